@@ -1,10 +1,16 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use tree_sitter::Parser;
 use walkdir::WalkDir;
 
+/// Key under which `doc_referenced_names` records usages from documentation
+/// that isn't tied to a single source file (standalone `.md`/`.rst`/
+/// `.ipynb`), as opposed to a docstring doctest, which is keyed by the file
+/// whose docstring it came from.
+const PROJECT_WIDE_DOCS: &str = "";
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DeadCodeItem {
     pub file: String,
@@ -12,46 +18,215 @@ pub struct DeadCodeItem {
     pub name: String,
     pub code_type: String,
     pub confidence: u8,
+    /// Size of the definition in source lines. 0 for imports.
     pub size: usize,
+    /// Size of the definition in bytes (`end_byte - start_byte`). 0 for imports.
+    pub size_bytes: usize,
+    /// 0-based column where the dead name starts, for caret diagnostics.
+    pub column: usize,
+    /// 0-based column where the dead name ends (exclusive).
+    pub end_column: usize,
 }
 
-pub struct DeadCodeAnalyzer {
+/// One recorded definition site: where it is, and which binding it
+/// resolves to for usage tracking.
+#[derive(Debug, Clone)]
+struct DefSite {
+    file: String,
+    line: usize,
+    column: usize,
+    end_column: usize,
+    binding_id: usize,
+    confidence: u8,
+    size_lines: usize,
+    size_bytes: usize,
+    suppressed: bool,
+}
+
+impl DefSite {
+    /// A site with no size/confidence heuristics of its own (imports).
+    /// Suppression is still honored, so `# noqa: dead-code` /
+    /// `# pragma: no cover` on or above an `import` line silences it too.
+    fn new(
+        file: String,
+        line: usize,
+        column: usize,
+        end_column: usize,
+        binding_id: usize,
+        content: &str,
+        def_row: usize,
+    ) -> Self {
+        Self {
+            file,
+            line,
+            column,
+            end_column,
+            binding_id,
+            confidence: 90,
+            size_lines: 0,
+            size_bytes: 0,
+            suppressed: DeadCodeAnalyzer::has_suppression_comment(content, def_row),
+        }
+    }
+}
+
+/// The kind of lexical scope a name can be bound in. Mirrors the scopes
+/// Python itself recognizes for name resolution: modules, classes,
+/// functions, and comprehensions (which get their own scope in Python 3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScopeKind {
+    Module,
+    Class,
+    Function,
+    Comprehension,
+}
+
+/// One binding introduced in a scope: a parameter, an assignment target,
+/// a `def`/`class` name, an import alias, or a `global`/`nonlocal` name.
+/// Each binding gets its own id so usages resolve to *this* definition
+/// site rather than to every definition sharing the same string.
+#[derive(Debug, Clone)]
+struct Binding {
     #[allow(dead_code)]
+    name: String,
+}
+
+/// A single entry on the scope stack while walking the tree. `bindings`
+/// maps a name to the id of the binding it refers to *in this scope*.
+struct Scope {
+    kind: ScopeKind,
+    bindings: HashMap<String, usize>,
+}
+
+impl Scope {
+    fn new(kind: ScopeKind) -> Self {
+        Self {
+            kind,
+            bindings: HashMap::new(),
+        }
+    }
+}
+
+pub struct DeadCodeAnalyzer {
     min_confidence: u8,
     #[allow(dead_code)]
     exclude_patterns: Vec<String>,
-    defined_names: HashMap<String, Vec<(String, usize)>>,
-    used_names: HashMap<String, usize>,
+    // name -> (file, line, binding_id) for every function/class definition found.
+    defined_names: HashMap<String, Vec<DefSite>>,
+    // binding id -> how many times it was referenced.
+    used_bindings: HashMap<usize, usize>,
     #[allow(dead_code)]
+    bindings: Vec<Binding>,
     results: Vec<DeadCodeItem>,
+
+    // --- reachability-from-entrypoints state (see `reachable_from_entrypoints`) ---
+    // caller binding -> every binding referenced inside its body.
+    call_edges: HashMap<usize, Vec<usize>>,
+    // bindings that are roots regardless of whether anything calls them:
+    // references made at module top level and `test_*` functions.
+    // `unittest.TestCase` methods and `__all__` names are added as roots
+    // later, in `reachable_from_entrypoints`.
+    root_bindings: HashSet<usize>,
+    // binding id of a class -> is it a `unittest.TestCase` subclass.
+    test_case_classes: HashSet<usize>,
+    // method binding id -> the binding id of the class that owns it.
+    method_class: HashMap<usize, usize>,
+    // file -> names listed in that file's `__all__`.
+    dunder_all: HashMap<String, HashSet<String>>,
+
+    // Traversal-local stacks, valid only while walking a single file.
+    def_stack: Vec<usize>,
+    class_stack: Vec<usize>,
+    // Path of the file currently being walked.
+    current_file: String,
+
+    // name -> every import binding site found for it.
+    imported_names: HashMap<String, Vec<DefSite>>,
+    // binding ids that are exempt from "unused import" reporting because
+    // they're re-exported (listed in `__all__`, or a bare
+    // `from . import x` inside an `__init__.py`).
+    reexport_exempt: HashSet<usize>,
+    // `from module import *` sites that can't be analyzed precisely.
+    wildcard_imports: Vec<(String, usize)>,
+
+    // Whether to also scan .md/.rst/.ipynb files and docstring doctests.
+    scan_docs: bool,
+    // Names referenced from documentation, keyed by the scope they keep
+    // alive: a docstring doctest only demonstrates its own file's API, so
+    // it's keyed by that file's path; a standalone .md/.rst/.ipynb isn't
+    // tied to one file, so it's recorded under `PROJECT_WIDE_DOCS`.
+    doc_referenced_names: HashMap<String, HashSet<String>>,
+    // Names referenced as a `.attribute` (e.g. `self.helper()`,
+    // `instance.helper()`). The scope resolver can't tell which binding a
+    // method call targets - `object` is resolved on its own below - so
+    // this is a weaker, name-only usage signal that keeps any same-named
+    // definition alive, the same way `doc_referenced_names` does.
+    attribute_referenced_names: HashSet<String>,
 }
 
 impl DeadCodeAnalyzer {
-    pub fn new(min_confidence: u8, exclude_patterns: Vec<&str>) -> Self {
+    pub fn new(min_confidence: u8, exclude_patterns: Vec<&str>, scan_docs: bool) -> Self {
         Self {
             min_confidence,
             exclude_patterns: exclude_patterns.iter().map(|s| s.to_string()).collect(),
             defined_names: HashMap::new(),
-            used_names: HashMap::new(),
+            used_bindings: HashMap::new(),
+            bindings: Vec::new(),
             results: Vec::new(),
+            call_edges: HashMap::new(),
+            root_bindings: HashSet::new(),
+            test_case_classes: HashSet::new(),
+            method_class: HashMap::new(),
+            dunder_all: HashMap::new(),
+            def_stack: Vec::new(),
+            class_stack: Vec::new(),
+            current_file: String::new(),
+            imported_names: HashMap::new(),
+            reexport_exempt: HashSet::new(),
+            wildcard_imports: Vec::new(),
+            scan_docs,
+            doc_referenced_names: HashMap::new(),
+            attribute_referenced_names: HashSet::new(),
         }
     }
 
     pub fn analyze_path(&mut self, path: &PathBuf) -> Result<()> {
         if path.is_file() {
-            self.analyze_file(path)?;
+            self.analyze_any_file(path)?;
         } else if path.is_dir() {
-            for entry in WalkDir::new(path)
+            // Collect before looping: `is_scannable` borrows `self`
+            // immutably, and `analyze_any_file` below needs `&mut self`,
+            // so the two borrows can't overlap in the same `for` loop.
+            let entries: Vec<PathBuf> = WalkDir::new(path)
                 .into_iter()
                 .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().map_or(false, |ext| ext == "py"))
-            {
-                self.analyze_file(&entry.path().to_path_buf())?;
+                .filter(|e| self.is_scannable(e.path()))
+                .map(|e| e.path().to_path_buf())
+                .collect();
+            for entry in entries {
+                self.analyze_any_file(&entry)?;
             }
         }
         Ok(())
     }
 
+    fn is_scannable(&self, path: &std::path::Path) -> bool {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("py") => true,
+            Some("md") | Some("rst") | Some("ipynb") => self.scan_docs,
+            _ => false,
+        }
+    }
+
+    fn analyze_any_file(&mut self, file_path: &PathBuf) -> Result<()> {
+        match file_path.extension().and_then(|ext| ext.to_str()) {
+            Some("py") => self.analyze_file(file_path),
+            Some("md") | Some("rst") => self.analyze_doc_file(file_path),
+            Some("ipynb") => self.analyze_notebook(file_path),
+            _ => Ok(()),
+        }
+    }
+
     fn analyze_file(&mut self, file_path: &PathBuf) -> Result<()> {
         let content = std::fs::read_to_string(file_path)?;
         let mut parser = Parser::new();
@@ -64,70 +239,890 @@ impl DeadCodeAnalyzer {
 
         let root = tree.root_node();
 
-        // Extract definitions and usage
-        self.extract_definitions(&root, &content, file_path);
-        self.extract_usage(&root, &content);
+        self.def_stack.clear();
+        self.class_stack.clear();
+        self.current_file = file_path.to_string_lossy().to_string();
+        self.collect_dunder_all(&root, &content, file_path);
+
+        let mut scopes = vec![Scope::new(ScopeKind::Module)];
+        self.collect_bindings(&root, &content, &mut scopes);
+        self.resolve_references(&root, &content, file_path, &mut scopes);
+
+        if self.scan_docs {
+            self.scan_doctests(&root, &content);
+        }
 
         Ok(())
     }
 
-    fn extract_definitions(&mut self, node: &tree_sitter::Node, content: &str, file_path: &PathBuf) {
-        if node.kind() == "function_definition" || node.kind() == "decorated_definition" {
-            if let Some(name_node) = node.child_by_field_name("name") {
-                let name = name_node.utf8_text(content.as_bytes()).unwrap_or("");
-                let line = node.start_position().row + 1;
-                let _size = node.end_byte() - node.start_byte();
-                
-                self.defined_names
-                    .entry(name.to_string())
-                    .or_insert_with(Vec::new)
-                    .push((file_path.to_string_lossy().to_string(), line));
-                
-                // Check for imports
-                if node.kind() == "import_statement" {
-                    self.defined_names
-                        .entry(name.to_string())
-                        .or_insert_with(Vec::new)
-                        .push((file_path.to_string_lossy().to_string(), line));
+    /// Finds every `string` literal node in the file and pulls `>>>`
+    /// doctest lines out of it (docstrings aren't distinguished from
+    /// other strings - close enough, since a `>>>` line only shows up in
+    /// something meant as a doctest).
+    fn scan_doctests(&mut self, node: &tree_sitter::Node, content: &str) {
+        if node.kind() == "string" {
+            if let Ok(text) = node.utf8_text(content.as_bytes()) {
+                if text.contains(">>>") {
+                    let snippet = Self::extract_doctest_snippet(text);
+                    if !snippet.is_empty() {
+                        let scope = self.current_file.clone();
+                        self.count_doc_usage(&snippet, &scope);
+                    }
                 }
             }
         }
-
         for child in node.children(&mut node.walk()) {
-            self.extract_definitions(&child, content, file_path);
+            self.scan_doctests(&child, content);
         }
     }
 
-    fn extract_usage(&mut self, node: &tree_sitter::Node, content: &str) {
-        if node.kind() == "identifier" || node.kind() == "attribute" {
-            if let Ok(text) = node.utf8_text(content.as_bytes()) {
-                *self.used_names.entry(text.to_string()).or_insert(0) += 1;
+    fn extract_doctest_snippet(text: &str) -> String {
+        let mut lines = Vec::new();
+        for line in text.lines() {
+            let trimmed = line.trim_start();
+            if let Some(code) = trimmed.strip_prefix(">>> ").or_else(|| trimmed.strip_prefix(">>>")) {
+                lines.push(code.to_string());
+            } else if let Some(code) = trimmed.strip_prefix("... ").or_else(|| trimmed.strip_prefix("...")) {
+                lines.push(code.to_string());
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Reads a `.md`/`.rst` file and feeds every fenced ```python block
+    /// through the same name-usage counting as doctests and notebooks.
+    fn analyze_doc_file(&mut self, file_path: &PathBuf) -> Result<()> {
+        let content = std::fs::read_to_string(file_path)?;
+        let mut in_block = false;
+        let mut block = String::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if !in_block {
+                if trimmed.eq_ignore_ascii_case("```python") || trimmed.eq_ignore_ascii_case("```py") {
+                    in_block = true;
+                    block.clear();
+                }
+            } else if trimmed == "```" {
+                in_block = false;
+                self.count_doc_usage(&block, PROJECT_WIDE_DOCS);
+            } else {
+                block.push_str(line);
+                block.push('\n');
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a Jupyter notebook's code cells and feeds their concatenated
+    /// source through the same name-usage counting as docs/doctests.
+    fn analyze_notebook(&mut self, file_path: &PathBuf) -> Result<()> {
+        let content = std::fs::read_to_string(file_path)?;
+        let notebook: serde_json::Value = match serde_json::from_str(&content) {
+            Ok(v) => v,
+            Err(_) => return Ok(()),
+        };
+
+        let cells = match notebook.get("cells").and_then(|c| c.as_array()) {
+            Some(cells) => cells,
+            None => return Ok(()),
+        };
+
+        let mut source = String::new();
+        for cell in cells {
+            if cell.get("cell_type").and_then(|t| t.as_str()) != Some("code") {
+                continue;
+            }
+            match cell.get("source") {
+                Some(serde_json::Value::Array(lines)) => {
+                    for line in lines {
+                        if let Some(text) = line.as_str() {
+                            source.push_str(text);
+                        }
+                    }
+                }
+                Some(serde_json::Value::String(text)) => source.push_str(text),
+                _ => {}
+            }
+            source.push('\n');
+        }
+
+        self.count_doc_usage(&source, PROJECT_WIDE_DOCS);
+        Ok(())
+    }
+
+    /// Parses an extracted snippet and records every name it references
+    /// under `scope` so project definitions demonstrated only in docs
+    /// aren't flagged. `scope` is the file whose docstring the snippet
+    /// came from, or `PROJECT_WIDE_DOCS` for standalone doc/notebook
+    /// sources that aren't tied to one file.
+    fn count_doc_usage(&mut self, snippet: &str, scope: &str) {
+        if snippet.trim().is_empty() {
+            return;
+        }
+
+        let mut parser = Parser::new();
+        let python_language = tree_sitter_python::language();
+        if parser.set_language(&python_language).is_err() {
+            return;
+        }
+        let tree = match parser.parse(snippet, None) {
+            Some(t) => t,
+            None => return,
+        };
+
+        let names = self.doc_referenced_names.entry(scope.to_string()).or_insert_with(HashSet::new);
+        Self::collect_referenced_names(&tree.root_node(), snippet, names);
+    }
+
+    /// Is `name`, as defined in `file`, kept alive by documentation? True
+    /// if that file's own docstring doctests reference it, or if it's
+    /// referenced from project-wide docs (a standalone .md/.rst/.ipynb).
+    fn is_doc_referenced(&self, file: &str, name: &str) -> bool {
+        self.doc_referenced_names.get(file).map_or(false, |n| n.contains(name))
+            || self
+                .doc_referenced_names
+                .get(PROJECT_WIDE_DOCS)
+                .map_or(false, |n| n.contains(name))
+    }
+
+    fn collect_referenced_names(node: &tree_sitter::Node, content: &str, names: &mut HashSet<String>) {
+        match node.kind() {
+            "identifier" => {
+                if let Ok(text) = node.utf8_text(content.as_bytes()) {
+                    names.insert(text.to_string());
+                }
+            }
+            _ => {
+                for child in node.children(&mut node.walk()) {
+                    Self::collect_referenced_names(&child, content, names);
+                }
+            }
+        }
+    }
+
+    /// Registers a new binding and records it under `name` in the
+    /// innermost scope. Returns the id so callers can mark it used.
+    fn bind(&mut self, scopes: &mut [Scope], name: &str) -> usize {
+        let id = self.bindings.len();
+        self.bindings.push(Binding {
+            name: name.to_string(),
+        });
+        scopes
+            .last_mut()
+            .expect("scope stack is never empty")
+            .bindings
+            .insert(name.to_string(), id);
+        id
+    }
+
+    /// Resolves `name` by walking the scope stack outward from the
+    /// innermost scope, the way Python's LEGB lookup works, and marks the
+    /// nearest matching binding as used. A class scope is only visited
+    /// when it's the innermost one (a reference made directly in the
+    /// class body); any class scope further out is skipped, since Python
+    /// doesn't let a nested function or comprehension see its enclosing
+    /// class's namespace implicitly.
+    fn resolve_and_use(&mut self, scopes: &[Scope], name: &str) -> Option<usize> {
+        let innermost = scopes.len().saturating_sub(1);
+        for (i, scope) in scopes.iter().enumerate().rev() {
+            if scope.kind == ScopeKind::Class && i != innermost {
+                continue;
+            }
+            if let Some(&id) = scope.bindings.get(name) {
+                *self.used_bindings.entry(id).or_insert(0) += 1;
+                return Some(id);
             }
         }
+        None
+    }
+
+    /// Checks whether a class's base-class list mentions `TestCase`,
+    /// e.g. `class FooTests(unittest.TestCase):`.
+    fn extends_test_case(node: &tree_sitter::Node, content: &str) -> bool {
+        node.child_by_field_name("superclasses")
+            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+            .map(|text| text.contains("TestCase"))
+            .unwrap_or(false)
+    }
+
+    /// Is `node` (a `function_definition` or `class_definition`) wrapped in
+    /// a `decorated_definition`? Any decorator - `@app.route`,
+    /// `@pytest.fixture`, `@property`, `@abstractmethod` - means a
+    /// framework may call the definition reflectively rather than through
+    /// a name reference we can see, so it's a weaker dead-code signal.
+    fn is_decorated(node: &tree_sitter::Node) -> bool {
+        node.parent()
+            .map_or(false, |parent| parent.kind() == "decorated_definition")
+    }
+
+    /// Is `name` a dunder method, e.g. `__init__` or `__enter__`?
+    fn is_dunder(name: &str) -> bool {
+        name.starts_with("__") && name.ends_with("__") && name.len() > 4
+    }
+
+    /// Heuristic confidence that a flagged definition is truly dead.
+    /// Dunder methods (`__init__`, `__enter__`) are invoked implicitly by
+    /// the interpreter and decorated definitions may be invoked
+    /// reflectively by a framework, so both lower confidence; a plain,
+    /// undecorated helper keeps the high default.
+    fn definition_confidence(name: &str, decorated: bool) -> u8 {
+        match (Self::is_dunder(name), decorated) {
+            (true, _) => 40,
+            (false, true) => 55,
+            (false, false) => 90,
+        }
+    }
+
+    /// Does the definition's own line or the line directly above it carry
+    /// an inline suppression marker (`# noqa: dead-code` / `# pragma: no
+    /// cover`)? Lets users silence individual findings without touching
+    /// `--min-confidence`.
+    fn has_suppression_comment(content: &str, def_row: usize) -> bool {
+        let line_is_suppressed = |line: &str| {
+            let lower = line.to_ascii_lowercase();
+            lower.contains('#')
+                && (lower.contains("noqa: dead-code")
+                    || lower.contains("noqa:dead-code")
+                    || lower.contains("pragma: no cover")
+                    || lower.contains("pragma: nocover"))
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        lines.get(def_row).map_or(false, |l| line_is_suppressed(l))
+            || (def_row > 0 && lines.get(def_row - 1).map_or(false, |l| line_is_suppressed(l)))
+    }
+
+    /// Does this scope kind introduce a new, nested lexical scope?
+    fn scope_kind_for(node_kind: &str) -> Option<ScopeKind> {
+        match node_kind {
+            "function_definition" | "lambda" => Some(ScopeKind::Function),
+            "class_definition" => Some(ScopeKind::Class),
+            "list_comprehension" | "set_comprehension" | "dictionary_comprehension"
+            | "generator_expression" => Some(ScopeKind::Comprehension),
+            _ => None,
+        }
+    }
+
+    /// Shallow pre-pass: collects every name bound directly in `node`'s
+    /// scope (parameters, assignment targets, def/class names, import
+    /// aliases, global/nonlocal declarations) without descending into
+    /// nested scopes. This has to run before any usage is resolved so
+    /// that forward references within the same scope (e.g. two sibling
+    /// functions calling each other) still resolve correctly.
+    fn collect_bindings(&mut self, node: &tree_sitter::Node, content: &str, scopes: &mut [Scope]) {
+        match node.kind() {
+            "function_definition" | "class_definition" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    if let Ok(name) = name_node.utf8_text(content.as_bytes()) {
+                        self.bind(scopes, name);
+                    }
+                }
+                // Don't descend into the body; it gets its own scope.
+                return;
+            }
+            "parameters" | "lambda_parameters" => {
+                for param in node.children(&mut node.walk()) {
+                    self.collect_parameter(&param, content, scopes);
+                }
+                return;
+            }
+            "assignment" | "augmented_assignment" => {
+                if let Some(target) = node.child_by_field_name("left") {
+                    self.collect_targets(&target, content, scopes);
+                }
+            }
+            "named_expression" => {
+                if let Some(target) = node.child_by_field_name("name") {
+                    self.collect_targets(&target, content, scopes);
+                }
+            }
+            "for_statement" | "for_in_clause" => {
+                if let Some(target) = node.child_by_field_name("left") {
+                    self.collect_targets(&target, content, scopes);
+                }
+            }
+            "with_item" => {
+                if let Some(alias) = node.child_by_field_name("alias") {
+                    self.collect_targets(&alias, content, scopes);
+                }
+            }
+            "except_clause" => {
+                if let Some(alias) = node.child_by_field_name("alias") {
+                    self.collect_targets(&alias, content, scopes);
+                }
+            }
+            // `with foo() as bar:` / `except E as bar:` may parse the
+            // "as bar" part as a generic as_pattern instead of a named
+            // field on the parent - handle both shapes.
+            "as_pattern" => {
+                if let Some(alias) = node.child_by_field_name("alias") {
+                    self.collect_targets(&alias, content, scopes);
+                }
+            }
+            "import_statement" => {
+                self.collect_import_names(node, content, scopes);
+            }
+            "import_from_statement" => {
+                self.collect_import_from_names(node, content, scopes);
+            }
+            "global_statement" | "nonlocal_statement" => {
+                for child in node.children(&mut node.walk()) {
+                    if child.kind() == "identifier" {
+                        if let Ok(name) = child.utf8_text(content.as_bytes()) {
+                            self.bind(scopes, name);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Recurse, but stop at the boundary of a nested scope - its own
+        // bindings belong to *its* scope, collected when we enter it.
+        if Self::scope_kind_for(node.kind()).is_some() && node.kind() != "class_definition" {
+            return;
+        }
 
         for child in node.children(&mut node.walk()) {
-            self.extract_usage(&child, content);
+            self.collect_bindings(&child, content, scopes);
+        }
+    }
+
+    fn collect_parameter(&mut self, node: &tree_sitter::Node, content: &str, scopes: &mut [Scope]) {
+        match node.kind() {
+            "identifier" => {
+                if let Ok(name) = node.utf8_text(content.as_bytes()) {
+                    self.bind(scopes, name);
+                }
+            }
+            "typed_parameter" | "default_parameter" | "typed_default_parameter"
+            | "list_splat_pattern" | "dictionary_splat_pattern" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    self.collect_parameter(&name_node, content, scopes);
+                } else {
+                    // Fall back to the first identifier child.
+                    for child in node.children(&mut node.walk()) {
+                        if child.kind() == "identifier" {
+                            self.collect_parameter(&child, content, scopes);
+                            break;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Binds every identifier inside an assignment/`for`/`with`/`except`
+    /// target, including tuple and list destructuring patterns.
+    fn collect_targets(&mut self, node: &tree_sitter::Node, content: &str, scopes: &mut [Scope]) {
+        match node.kind() {
+            "identifier" => {
+                if let Ok(name) = node.utf8_text(content.as_bytes()) {
+                    self.bind(scopes, name);
+                }
+            }
+            "tuple_pattern" | "list_pattern" | "pattern_list" | "tuple" | "list" => {
+                for child in node.named_children(&mut node.walk()) {
+                    self.collect_targets(&child, content, scopes);
+                }
+            }
+            // `a.b = 1` or `a[0] = 1` don't bind a new name.
+            "attribute" | "subscript" => {}
+            _ => {
+                for child in node.named_children(&mut node.walk()) {
+                    self.collect_targets(&child, content, scopes);
+                }
+            }
+        }
+    }
+
+    fn collect_import_names(&mut self, node: &tree_sitter::Node, content: &str, scopes: &mut [Scope]) {
+        for child in node.named_children(&mut node.walk()) {
+            match child.kind() {
+                "aliased_import" => {
+                    if let Some(alias) = child.child_by_field_name("alias") {
+                        self.bind_import(&alias, content, scopes);
+                    }
+                }
+                "dotted_name" => {
+                    // `import a.b.c` binds the top-level name `a`.
+                    if let Some(first) = child.named_child(0) {
+                        self.bind_import(&first, content, scopes);
+                    } else {
+                        self.bind_import(&child, content, scopes);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_import_from_names(&mut self, node: &tree_sitter::Node, content: &str, scopes: &mut [Scope]) {
+        // `from . import foo` / `from .. import foo`: a bare relative
+        // import with no dotted module name, the idiom `__init__.py`
+        // uses to re-export a submodule's names.
+        let module_name = node.child_by_field_name("module_name");
+        // `relative_import` also covers `from .sub import foo` (a
+        // `dotted_name` child alongside the dots) - only a bare `.`/`..`
+        // with no such child is the re-export idiom.
+        let is_bare_relative = module_name.map_or(false, |m| {
+            m.kind() == "relative_import"
+                && m.named_children(&mut m.walk()).all(|c| c.kind() != "dotted_name")
+        });
+        let is_init_py = std::path::Path::new(&self.current_file)
+            .file_name()
+            .and_then(|f| f.to_str())
+            .map_or(false, |f| f == "__init__.py");
+
+        for child in node.named_children(&mut node.walk()) {
+            // `module_name` (`collections` in `from collections import
+            // Foo`) is the module being imported *from*, not a bound
+            // name - skip it so it isn't mistaken for an imported name.
+            if module_name.map_or(false, |m| m.id() == child.id()) {
+                continue;
+            }
+            match child.kind() {
+                "aliased_import" => {
+                    if let Some(alias) = child.child_by_field_name("alias") {
+                        let id = self.bind_import(&alias, content, scopes);
+                        if is_bare_relative && is_init_py {
+                            if let Some(id) = id {
+                                self.reexport_exempt.insert(id);
+                            }
+                        }
+                    }
+                }
+                "dotted_name" => {
+                    let id = self.bind_import(&child, content, scopes);
+                    if is_bare_relative && is_init_py {
+                        if let Some(id) = id {
+                            self.reexport_exempt.insert(id);
+                        }
+                    }
+                }
+                "wildcard_import" => {
+                    let line = node.start_position().row + 1;
+                    self.wildcard_imports.push((self.current_file.clone(), line));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Binds an imported name both into the current scope (for usage
+    /// resolution) and into `imported_names` (for unused-import
+    /// reporting), recording its exact span for caret diagnostics.
+    fn bind_import(
+        &mut self,
+        name_node: &tree_sitter::Node,
+        content: &str,
+        scopes: &mut [Scope],
+    ) -> Option<usize> {
+        let name = name_node.utf8_text(content.as_bytes()).ok()?.to_string();
+        let binding_id = self.bind(scopes, &name);
+        self.imported_names.entry(name).or_insert_with(Vec::new).push(DefSite::new(
+            self.current_file.clone(),
+            name_node.start_position().row + 1,
+            name_node.start_position().column,
+            name_node.end_position().column,
+            binding_id,
+            content,
+            name_node.start_position().row,
+        ));
+        Some(binding_id)
+    }
+
+    /// Scans direct module-level statements for a top-level `__all__ =
+    /// [...]` assignment and records the string literals it lists, so
+    /// reachability analysis can treat exported names as entry points.
+    fn collect_dunder_all(&mut self, root: &tree_sitter::Node, content: &str, file_path: &PathBuf) {
+        for stmt in root.children(&mut root.walk()) {
+            let assignment = if stmt.kind() == "expression_statement" {
+                stmt.named_child(0)
+            } else {
+                Some(stmt)
+            };
+
+            let assignment = match assignment {
+                Some(a) if a.kind() == "assignment" => a,
+                _ => continue,
+            };
+            match assignment.child_by_field_name("left") {
+                Some(l) if l.kind() == "identifier" && l.utf8_text(content.as_bytes()) == Ok("__all__") => {}
+                _ => continue,
+            };
+            let right = match assignment.child_by_field_name("right") {
+                Some(r) => r,
+                None => continue,
+            };
+
+            let mut names = HashSet::new();
+            for item in right.named_children(&mut right.walk()) {
+                if item.kind() == "string" {
+                    if let Some(text) = Self::string_literal_value(&item, content) {
+                        names.insert(text);
+                    }
+                }
+            }
+            self.dunder_all
+                .entry(file_path.to_string_lossy().to_string())
+                .or_insert_with(HashSet::new)
+                .extend(names);
         }
     }
 
-    pub fn get_results(&self) -> Vec<DeadCodeItem> {
-        let mut results = Vec::new();
+    /// Strips the quotes off a `string` node, ignoring f-strings/prefixes.
+    fn string_literal_value(node: &tree_sitter::Node, content: &str) -> Option<String> {
+        let text = node.utf8_text(content.as_bytes()).ok()?;
+        let trimmed = text.trim_matches(|c| c == '\'' || c == '"');
+        Some(trimmed.to_string())
+    }
+
+    /// Collects the bindings owned by the scope rooted at `node` (its
+    /// parameters plus its body), then resolves every reference inside
+    /// that body against the now-complete scope stack. Called each time a
+    /// nested function/class/comprehension/lambda is entered; the module
+    /// scope gets the same two passes directly in `analyze_file`.
+    fn walk_scope(
+        &mut self,
+        node: &tree_sitter::Node,
+        content: &str,
+        file_path: &PathBuf,
+        scopes: &mut Vec<Scope>,
+    ) {
+        if let Some(params) = node.child_by_field_name("parameters") {
+            self.collect_bindings(&params, content, scopes);
+        }
+
+        // Comprehensions bind their loop variable in a `for_in_clause`
+        // sibling of `body`, so they need every child scanned; functions,
+        // lambdas and classes have a single `body` field to scan instead.
+        let content_nodes: Vec<tree_sitter::Node> = match node.kind() {
+            "list_comprehension" | "set_comprehension" | "dictionary_comprehension"
+            | "generator_expression" => node.children(&mut node.walk()).collect(),
+            _ => node
+                .child_by_field_name("body")
+                .map(|b| vec![b])
+                .unwrap_or_else(|| node.children(&mut node.walk()).collect()),
+        };
+
+        for child in &content_nodes {
+            self.collect_bindings(child, content, scopes);
+        }
+        for child in &content_nodes {
+            self.resolve_references(child, content, file_path, scopes);
+        }
+    }
+
+    /// Walks `node`'s descendants resolving identifier references and
+    /// entering nested scopes (recording their definitions) as they're
+    /// found. Does not itself perform the binding pre-pass for `node` -
+    /// that's `walk_scope`'s job (or `analyze_file`'s, for the module
+    /// scope), run once per scope before this starts.
+    fn resolve_references(
+        &mut self,
+        node: &tree_sitter::Node,
+        content: &str,
+        file_path: &PathBuf,
+        scopes: &mut Vec<Scope>,
+    ) {
+        match node.kind() {
+            "function_definition" | "class_definition" => {
+                let name_field = node.child_by_field_name("name");
+                let mut this_binding = None;
+                if let Some(name_node) = name_field {
+                    if let Ok(name) = name_node.utf8_text(content.as_bytes()) {
+                        // Already bound in the enclosing scope's pre-pass.
+                        if let Some(&binding_id) = scopes
+                            .last()
+                            .and_then(|s| s.bindings.get(name))
+                        {
+                            let line = node.start_position().row + 1;
+                            let size_lines = node.end_position().row - node.start_position().row + 1;
+                            let size_bytes = node.end_byte() - node.start_byte();
+                            let decorated = Self::is_decorated(node);
+                            let confidence = Self::definition_confidence(name, decorated);
+                            let suppressed = Self::has_suppression_comment(content, node.start_position().row);
+                            self.defined_names
+                                .entry(name.to_string())
+                                .or_insert_with(Vec::new)
+                                .push(DefSite {
+                                    file: file_path.to_string_lossy().to_string(),
+                                    line,
+                                    column: name_node.start_position().column,
+                                    end_column: name_node.end_position().column,
+                                    binding_id,
+                                    confidence,
+                                    size_lines,
+                                    size_bytes,
+                                    suppressed,
+                                });
+                            this_binding = Some(binding_id);
+
+                            if node.kind() == "function_definition" && name.starts_with("test_") {
+                                self.root_bindings.insert(binding_id);
+                            }
+                            if node.kind() == "class_definition" && Self::extends_test_case(node, content) {
+                                self.test_case_classes.insert(binding_id);
+                            }
+                            if node.kind() == "function_definition" {
+                                if let Some(&class_id) = self.class_stack.last() {
+                                    self.method_class.insert(binding_id, class_id);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                let kind = Self::scope_kind_for(node.kind()).unwrap();
+                scopes.push(Scope::new(kind));
+                if let Some(id) = this_binding {
+                    self.def_stack.push(id);
+                    if node.kind() == "class_definition" {
+                        self.class_stack.push(id);
+                    }
+                }
+                self.walk_scope(node, content, file_path, scopes);
+                if this_binding.is_some() {
+                    self.def_stack.pop();
+                    if node.kind() == "class_definition" {
+                        self.class_stack.pop();
+                    }
+                }
+                scopes.pop();
+            }
+            "lambda" | "list_comprehension" | "set_comprehension" | "dictionary_comprehension"
+            | "generator_expression" => {
+                let kind = Self::scope_kind_for(node.kind()).unwrap();
+                scopes.push(Scope::new(kind));
+                self.walk_scope(node, content, file_path, scopes);
+                scopes.pop();
+            }
+            "identifier" => {
+                if let Ok(text) = node.utf8_text(content.as_bytes()) {
+                    if let Some(target) = self.resolve_and_use(scopes, text) {
+                        match self.def_stack.last() {
+                            Some(&caller) if caller != target => {
+                                self.call_edges.entry(caller).or_insert_with(Vec::new).push(target);
+                            }
+                            None => {
+                                self.root_bindings.insert(target);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            "attribute" => {
+                // The left-most object is a name reference, scope-resolved
+                // like any other identifier.
+                if let Some(object) = node.child_by_field_name("object") {
+                    self.resolve_references(&object, content, file_path, scopes);
+                }
+                // The `.attribute` part (e.g. `helper` in `self.helper()`)
+                // isn't scope-resolvable - we don't track instance
+                // membership - but it's how most methods are actually
+                // called, so record the bare name as a usage signal.
+                if let Some(attr) = node.child_by_field_name("attribute") {
+                    if let Ok(name) = attr.utf8_text(content.as_bytes()) {
+                        self.attribute_referenced_names.insert(name.to_string());
+                    }
+                }
+            }
+            "import_statement" | "import_from_statement" => {
+                // The bound name/alias here (`os` in `import os`, `x` in
+                // `import sys as x`) is a definition, not a usage -
+                // already recorded by `collect_bindings`/`bind_import`.
+                // Recursing into it like any other identifier would
+                // resolve the import's own binding against itself and
+                // mark it permanently "used", the same self-reference bug
+                // def/class names are excluded from above.
+            }
+            _ => {
+                for child in node.children(&mut node.walk()) {
+                    self.resolve_references(&child, content, file_path, scopes);
+                }
+            }
+        }
+    }
+
+    /// Computes every binding reachable from an entry point: names used
+    /// directly at module top level (including inside `if __name__ ==
+    /// "__main__":`), `test_*` functions and `unittest.TestCase` methods,
+    /// and names listed in a module's `__all__`. Reachability then
+    /// follows the call graph built while walking each file's body.
+    pub fn reachable_from_entrypoints(&self) -> HashSet<usize> {
+        let mut roots = self.root_bindings.clone();
+
+        for (&method_id, class_id) in &self.method_class {
+            if self.test_case_classes.contains(class_id) {
+                roots.insert(method_id);
+            }
+        }
 
         for (name, locations) in &self.defined_names {
-            if !self.used_names.contains_key(name) && !name.starts_with('_') {
-                for (file, line) in locations {
-                    results.push(DeadCodeItem {
-                        file: file.clone(),
-                        line: *line,
-                        name: name.clone(),
-                        code_type: "function/class".to_string(),
-                        confidence: 80,
-                        size: 0,
-                    });
+            for site in locations {
+                if self.dunder_all.get(&site.file).map_or(false, |names| names.contains(name)) {
+                    roots.insert(site.binding_id);
                 }
             }
         }
 
-        results
+        let mut reachable: HashSet<usize> = HashSet::new();
+        let mut worklist: Vec<usize> = roots.into_iter().collect();
+        while let Some(id) = worklist.pop() {
+            if !reachable.insert(id) {
+                continue;
+            }
+            if let Some(callees) = self.call_edges.get(&id) {
+                worklist.extend(callees.iter().copied());
+            }
+        }
+        reachable
+    }
+
+    /// Like `get_results`, but flags bindings that aren't transitively
+    /// reachable from any entry point rather than bindings with a zero
+    /// use count - this catches clusters of functions that only
+    /// reference each other but that nothing live ever calls. Only
+    /// function/class definitions are reported; unused imports are not.
+    pub fn get_unreachable_results(&mut self) -> Vec<DeadCodeItem> {
+        let reachable = self.reachable_from_entrypoints();
+        self.results.clear();
+
+        for (name, locations) in &self.defined_names {
+            // A leading underscore marks a module-private helper to
+            // skip, but not a dunder method - those get their own (low)
+            // confidence from `definition_confidence` instead of being
+            // hidden outright.
+            if name.starts_with('_') && !Self::is_dunder(name) {
+                continue;
+            }
+            for site in locations {
+                if reachable.contains(&site.binding_id)
+                    || self.is_doc_referenced(&site.file, name)
+                    || self.attribute_referenced_names.contains(name)
+                    || site.suppressed
+                    || site.confidence < self.min_confidence
+                {
+                    continue;
+                }
+
+                self.results.push(DeadCodeItem {
+                    file: site.file.clone(),
+                    line: site.line,
+                    name: name.clone(),
+                    code_type: "function/class".to_string(),
+                    confidence: site.confidence,
+                    size: site.size_lines,
+                    size_bytes: site.size_bytes,
+                    column: site.column,
+                    end_column: site.end_column,
+                });
+            }
+        }
+
+        self.results.clone()
+    }
+
+    pub fn get_results(&mut self) -> Vec<DeadCodeItem> {
+        self.results.clear();
+
+        for (name, locations) in &self.defined_names {
+            // A leading underscore marks a module-private helper to
+            // skip, but not a dunder method - those get their own (low)
+            // confidence from `definition_confidence` instead of being
+            // hidden outright.
+            if name.starts_with('_') && !Self::is_dunder(name) {
+                continue;
+            }
+            for site in locations {
+                if self.used_bindings.contains_key(&site.binding_id)
+                    || self.is_doc_referenced(&site.file, name)
+                    || self.attribute_referenced_names.contains(name)
+                    || site.suppressed
+                    || site.confidence < self.min_confidence
+                {
+                    continue;
+                }
+
+                self.results.push(DeadCodeItem {
+                    file: site.file.clone(),
+                    line: site.line,
+                    name: name.clone(),
+                    code_type: "function/class".to_string(),
+                    confidence: site.confidence,
+                    size: site.size_lines,
+                    size_bytes: site.size_bytes,
+                    column: site.column,
+                    end_column: site.end_column,
+                });
+            }
+        }
+
+        self.collect_unused_imports();
+
+        self.results.clone()
+    }
+
+    /// Appends unused-import findings to `self.results`: bindings from
+    /// `import`/`from ... import` that are never referenced, skipping
+    /// ones re-exported via `__all__` or a bare `from . import x` in an
+    /// `__init__.py`. Wildcard imports are reported separately since we
+    /// can't tell which names they actually bind.
+    fn collect_unused_imports(&mut self) {
+        for (name, locations) in &self.imported_names {
+            for site in locations {
+                if self.used_bindings.contains_key(&site.binding_id)
+                    || self.reexport_exempt.contains(&site.binding_id)
+                    || self.is_doc_referenced(&site.file, name)
+                    || site.suppressed
+                    || site.confidence < self.min_confidence
+                    || self
+                        .dunder_all
+                        .get(&site.file)
+                        .map_or(false, |names| names.contains(name))
+                {
+                    continue;
+                }
+
+                self.results.push(DeadCodeItem {
+                    file: site.file.clone(),
+                    line: site.line,
+                    name: name.clone(),
+                    code_type: "import".to_string(),
+                    confidence: site.confidence,
+                    size: site.size_lines,
+                    size_bytes: site.size_bytes,
+                    column: site.column,
+                    end_column: site.end_column,
+                });
+            }
+        }
+
+        for (file, line) in &self.wildcard_imports {
+            // Not a zero-confidence guess - we genuinely can't tell which
+            // names a `from x import *` binds, so it's always worth
+            // surfacing as a "cannot analyze" note. Its confidence sits
+            // just above the default `--min-confidence` so it shows up
+            // out of the box, while a stricter threshold can still hide it.
+            const WILDCARD_IMPORT_CONFIDENCE: u8 = 65;
+            if WILDCARD_IMPORT_CONFIDENCE < self.min_confidence {
+                continue;
+            }
+            self.results.push(DeadCodeItem {
+                file: file.clone(),
+                line: *line,
+                name: "*".to_string(),
+                code_type: "import-wildcard".to_string(),
+                confidence: WILDCARD_IMPORT_CONFIDENCE,
+                size: 0,
+                size_bytes: 0,
+                column: 0,
+                end_column: 0,
+            });
+        }
     }
 }
@@ -1,7 +1,17 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use colored::*;
 use std::path::PathBuf;
-use pydeadcode::analyzer::DeadCodeAnalyzer;
+use pydeadcode::analyzer::{DeadCodeAnalyzer, DeadCodeItem};
+
+/// How to render non-JSON results.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum OutputFormat {
+    /// One colored line per item (the original output).
+    #[default]
+    Compact,
+    /// rustc-style snippet with surrounding context and a caret underline.
+    Rich,
+}
 
 #[derive(Parser, Debug)]
 #[command(name = "pydeadcode")]
@@ -26,6 +36,21 @@ struct Args {
     /// Exclude patterns (comma-separated)
     #[arg(short, long)]
     exclude: Option<String>,
+
+    /// Report names unreachable from any entry point (module top level,
+    /// `if __name__ == "__main__":`, `__all__`, tests) instead of names
+    /// with a zero use count
+    #[arg(long)]
+    reachable_from_entrypoints: bool,
+
+    /// Output rendering for non-JSON results
+    #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+    format: OutputFormat,
+
+    /// Also scan .md/.rst docs, .ipynb notebooks, and docstring doctests
+    /// for names that keep code alive
+    #[arg(long)]
+    scan_docs: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -42,13 +67,17 @@ fn main() -> anyhow::Result<()> {
         .map(|s| s.split(',').collect())
         .unwrap_or_default();
 
-    let mut analyzer = DeadCodeAnalyzer::new(args.min_confidence, exclude_patterns);
+    let mut analyzer = DeadCodeAnalyzer::new(args.min_confidence, exclude_patterns, args.scan_docs);
 
     for path in &args.paths {
         analyzer.analyze_path(path)?;
     }
 
-    let results = analyzer.get_results();
+    let results = if args.reachable_from_entrypoints {
+        analyzer.get_unreachable_results()
+    } else {
+        analyzer.get_results()
+    };
 
     if results.is_empty() {
         println!("{}", "✓ No dead code found!".green());
@@ -70,18 +99,80 @@ fn main() -> anyhow::Result<()> {
 
         println!("{}", "\nDead Code Found:\n".yellow().bold());
         for result in &sorted_results {
-            println!(
-                "{}: {} - {} {} ({}% confidence)",
-                result.file.bright_blue(),
-                format!("line {}", result.line).cyan(),
-                result.name.red(),
-                format!("[{}]", result.code_type).dimmed(),
-                result.confidence
-            );
+            match args.format {
+                OutputFormat::Compact => print_compact(result),
+                OutputFormat::Rich => print_rich(result),
+            }
         }
 
         println!("\n{} dead code items found", sorted_results.len().to_string().yellow());
     }
 
     Ok(())
+}
+
+fn print_compact(result: &DeadCodeItem) {
+    println!(
+        "{}: {} - {} {} ({}% confidence)",
+        result.file.bright_blue(),
+        format!("line {}", result.line).cyan(),
+        result.name.red(),
+        format!("[{}]", result.code_type).dimmed(),
+        result.confidence
+    );
+}
+
+/// Renders a `rustc`-style snippet: the offending line (plus a line of
+/// context on either side) with a caret underline under the dead name's
+/// exact span.
+fn print_rich(result: &DeadCodeItem) {
+    println!(
+        "{} {}:{}:{}",
+        "-->".blue().bold(),
+        result.file,
+        result.line,
+        result.column + 1
+    );
+
+    let source = match std::fs::read_to_string(&result.file) {
+        Ok(source) => source,
+        Err(_) => {
+            // Source may have moved since analysis; fall back to compact.
+            print_compact(result);
+            return;
+        }
+    };
+    let lines: Vec<&str> = source.lines().collect();
+    let target = result.line.saturating_sub(1);
+    let first = target.saturating_sub(1);
+    let last = (target + 1).min(lines.len().saturating_sub(1));
+
+    let gutter_width = (last + 1).to_string().len();
+    for (i, line_no) in (first..=last).enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let line = match lines.get(line_no) {
+            Some(line) => line,
+            None => continue,
+        };
+        println!("{:>width$} | {}", line_no + 1, line, width = gutter_width);
+
+        if line_no == target {
+            let span = result.end_column.saturating_sub(result.column).max(1);
+            let underline = format!(
+                "{}{}",
+                " ".repeat(result.column),
+                "^".repeat(span)
+            );
+            println!(
+                "{:>width$} | {} {}",
+                "",
+                underline.red().bold(),
+                format!("`{}` is never used", result.name).dimmed(),
+                width = gutter_width
+            );
+        }
+    }
+    println!();
 }
\ No newline at end of file